@@ -17,7 +17,9 @@ use serde::{Deserialize, Serialize};
 #[cfg(feature = "std")]
 use {
     core::str::FromStr,
-    humantime::format_rfc3339_nanos,
+    humantime::{
+        format_rfc3339_micros, format_rfc3339_millis, format_rfc3339_nanos, format_rfc3339_seconds,
+    },
     std::time::{SystemTime, UNIX_EPOCH},
 };
 
@@ -31,6 +33,19 @@ const FRAC_MASK: u64 = 0xFFFF_FFFFu64;
 // number of nanoseconds in 1 second
 const NANO_PER_SEC: u64 = 1_000_000_000;
 
+// Pushes `nanos` as a decimal fraction-of-second, trimming trailing zeros but keeping at
+// least 1 digit (e.g. 129_693_000 -> "129693", 0 -> "0").
+#[cfg(feature = "std")]
+fn push_subsec_digits(out: &mut String, nanos: u32) {
+    let digits = format!("{nanos:09}");
+    let trimmed = digits.trim_end_matches('0');
+    if trimmed.is_empty() {
+        out.push('0');
+    } else {
+        out.push_str(trimmed);
+    }
+}
+
 /// A NTP 64-bits format as specified in
 /// [RFC-5909](https://tools.ietf.org/html/rfc5905#section-6)
 ///
@@ -51,7 +66,7 @@ const NANO_PER_SEC: u64 = 1_000_000_000;
 /// The size of this counter is currently hard-coded as [`crate::CSIZE`].
 ///
 /// ## Conversion to/from String
-/// 2 different String representations are supported:
+/// Several String representations are supported:
 /// 1. **as an unsigned integer in decimal format**
 ///   - Such conversion is lossless and thus bijective.
 ///   - NTP64 to String: use [`std::fmt::Display::fmt()`] or [`std::string::ToString::to_string()`].
@@ -61,6 +76,14 @@ const NANO_PER_SEC: u64 = 1_000_000_000;
 ///   - As a consequence it's not bijective: a NTP64 converted to RFC3339 String and then converted back to NTP64 might result to a different time.
 ///   - NTP64 to String: use [`std::fmt::Display::fmt()`] with the alternate flag (`{:#}`) or [`NTP64::to_string_rfc3339_lossy()`].
 ///   - String to NTP64: use [`NTP64::parse_rfc3339()`]
+///   - [`NTP64::to_string_rfc3339()`] additionally lets you pick the fractional-second [`Precision`]
+///     (`Smart`, `Seconds`, `Millis`, `Micros` or `Nanos`) instead of always showing nanoseconds.
+/// 3. **as a custom strftime-like format**: [`NTP64::format()`] renders arbitrary `%Y`/`%m`/`%d`/
+///    `%H`/`%M`/`%S`/`%f`/`%.f` directives, for callers that need a layout other than RFC3339.
+/// 4. **as a human-readable duration** (when this NTP64 represents an elapsed span rather than a
+///    timestamp, see "On EPOCH" below): [`NTP64::to_human_duration_string()`] and
+///    [`NTP64::parse_human_duration()`] convert to and from compact strings like `"2h 30m 5s 125ms"`.
+///    As with RFC3339, the fraction is truncated to nanoseconds, so this is not bijective.
 ///
 /// ## On EPOCH
 /// This timestamp in actually similar to a [`std::time::Duration`], as it doesn't define an EPOCH.  
@@ -123,21 +146,170 @@ impl NTP64 {
         format_rfc3339_nanos(self.to_system_time()).to_string()
     }
 
+    /// Convert to a RFC3339 time representation, with the fractional seconds part formatted
+    /// according to `precision`.
+    /// e.g. with [`Precision::Seconds`]: `"2024-07-01T13:51:12Z"`,
+    /// with [`Precision::Nanos`]: `"2024-07-01T13:51:12.129693000Z"`.
+    #[cfg(feature = "std")]
+    pub fn to_string_rfc3339(&self, precision: Precision) -> String {
+        let time = self.to_system_time();
+        match precision {
+            Precision::Smart => {
+                if self.subsec_nanos() == 0 {
+                    format_rfc3339_seconds(time).to_string()
+                } else {
+                    format_rfc3339_nanos(time).to_string()
+                }
+            }
+            Precision::Seconds => format_rfc3339_seconds(time).to_string(),
+            Precision::Millis => format_rfc3339_millis(time).to_string(),
+            Precision::Micros => format_rfc3339_micros(time).to_string(),
+            Precision::Nanos => format_rfc3339_nanos(time).to_string(),
+        }
+    }
+
+    /// Formats this NTP64 (interpreted as relative to [`UNIX_EPOCH`]) using a small set of
+    /// strftime-like directives:
+    /// - `%Y`, `%m`, `%d`: year, month, day
+    /// - `%H`, `%M`, `%S`: hour, minute, second
+    /// - `%f`: the subsecond part as digits (at least 1 digit, trailing zeros trimmed)
+    /// - `%.f`: like `%f`, but preceded by a `.` and empty when the subsecond part is zero
+    /// - `%%`: a literal `%`
+    ///
+    /// This lets one build formats like `"%H:%M:%S%.f"`, rendering `"13:51:12"` for whole
+    /// seconds and `"13:51:12.129693"` when there is a fraction. Any other `%`-directive is
+    /// passed through verbatim.
+    #[cfg(feature = "std")]
+    pub fn format(&self, fmt: &str) -> String {
+        // Leverage the RFC3339 formatting (which already handles calendar conversion) to
+        // extract the individual date/time fields by slicing the fixed-width result.
+        let rfc3339 = format_rfc3339_nanos(self.to_system_time()).to_string();
+        let year = &rfc3339[0..4];
+        let month = &rfc3339[5..7];
+        let day = &rfc3339[8..10];
+        let hour = &rfc3339[11..13];
+        let minute = &rfc3339[14..16];
+        let second = &rfc3339[17..19];
+        let nanos = self.subsec_nanos();
+
+        let mut out = String::new();
+        let mut chars = fmt.chars().peekable();
+        while let Some(c) = chars.next() {
+            if c != '%' {
+                out.push(c);
+                continue;
+            }
+            match chars.peek() {
+                Some('.') => {
+                    let mut lookahead = chars.clone();
+                    lookahead.next();
+                    if lookahead.peek() == Some(&'f') {
+                        chars.next();
+                        chars.next();
+                        if nanos != 0 {
+                            out.push('.');
+                            push_subsec_digits(&mut out, nanos);
+                        }
+                    } else {
+                        out.push('%');
+                    }
+                }
+                Some('f') => {
+                    chars.next();
+                    push_subsec_digits(&mut out, nanos);
+                }
+                Some('Y') => {
+                    chars.next();
+                    out.push_str(year);
+                }
+                Some('m') => {
+                    chars.next();
+                    out.push_str(month);
+                }
+                Some('d') => {
+                    chars.next();
+                    out.push_str(day);
+                }
+                Some('H') => {
+                    chars.next();
+                    out.push_str(hour);
+                }
+                Some('M') => {
+                    chars.next();
+                    out.push_str(minute);
+                }
+                Some('S') => {
+                    chars.next();
+                    out.push_str(second);
+                }
+                Some('%') => {
+                    chars.next();
+                    out.push('%');
+                }
+                _ => out.push('%'),
+            }
+        }
+        out
+    }
+
     /// Parse a RFC3339 time representation into a NTP64.
     #[cfg(feature = "std")]
     pub fn parse_rfc3339(s: &str) -> Result<Self, ParseNTP64Error> {
         match humantime::parse_rfc3339(s) {
             Ok(time) => time
                 .duration_since(UNIX_EPOCH)
-                .map(NTP64::from)
                 .map_err(|e| ParseNTP64Error {
                     cause: format!("Failed to parse '{s}' : {e}"),
-                }),
+                })
+                .and_then(NTP64::try_from),
             Err(_) => Err(ParseNTP64Error {
                 cause: format!("Failed to parse '{s}' : invalid RFC3339 format"),
             }),
         }
     }
+
+    /// Checked addition. Computes `self + other`, returning `None` if overflow occurred.
+    #[inline]
+    pub fn checked_add(self, other: Self) -> Option<Self> {
+        self.0.checked_add(other.0).map(Self)
+    }
+
+    /// Checked subtraction. Computes `self - other`, returning `None` if overflow occurred.
+    #[inline]
+    pub fn checked_sub(self, other: Self) -> Option<Self> {
+        self.0.checked_sub(other.0).map(Self)
+    }
+
+    /// Saturating addition. Computes `self + other`, saturating at `NTP64(u64::MAX)` instead of overflowing.
+    #[inline]
+    pub fn saturating_add(self, other: Self) -> Self {
+        Self(self.0.saturating_add(other.0))
+    }
+
+    /// Saturating subtraction. Computes `self - other`, saturating at `NTP64(0)` instead of overflowing.
+    #[inline]
+    pub fn saturating_sub(self, other: Self) -> Self {
+        Self(self.0.saturating_sub(other.0))
+    }
+
+    /// Formats this NTP64 -- interpreted as an elapsed duration rather than a timestamp, as
+    /// there's no EPOCH (see "On EPOCH" above) -- into a compact human-readable string, e.g.
+    /// `"2h 30m 5s 129ms"`.
+    #[cfg(feature = "std")]
+    pub fn to_human_duration_string(&self) -> String {
+        humantime::format_duration(self.to_duration()).to_string()
+    }
+
+    /// Parses a human-readable duration string, as produced by
+    /// [`NTP64::to_human_duration_string()`] (e.g. `"2h 30m 5s 129ms"`), into a [`NTP64`].
+    #[cfg(feature = "std")]
+    pub fn parse_human_duration(s: &str) -> Result<Self, ParseNTP64Error> {
+        humantime::parse_duration(s)
+            .map_err(|e| ParseNTP64Error {
+                cause: format!("Failed to parse '{s}' as a human duration : {e}"),
+            })
+            .and_then(NTP64::try_from)
+    }
 }
 
 impl Add for NTP64 {
@@ -275,12 +447,42 @@ impl fmt::Debug for NTP64 {
     }
 }
 
-impl From<Duration> for NTP64 {
-    fn from(duration: Duration) -> NTP64 {
+impl NTP64 {
+    /// Converts a [`Duration`] into a [`NTP64`], saturating at [`MAX_NB_SEC`] seconds if the
+    /// duration's number of seconds doesn't fit in the 32-bits Seconds part of the NTP64.
+    ///
+    /// This is an inherent method rather than a [`From`] impl: `core` provides a blanket
+    /// `impl<T, U: Into<T>> TryFrom<U> for T`, so an infallible `From<Duration>` would conflict
+    /// with [`NTP64::try_from()`] below. Use [`NTP64::try_from()`] instead if you need to detect
+    /// and reject out-of-range durations rather than silently saturating.
+    pub fn saturating_from_duration(duration: Duration) -> Self {
+        let secs = duration.as_secs().min(MAX_NB_SEC);
+        let nanos: u64 = duration.subsec_nanos().into();
+        NTP64((secs << 32) + ((nanos * FRAC_PER_SEC) / NANO_PER_SEC))
+    }
+}
+
+impl TryFrom<Duration> for NTP64 {
+    type Error = ParseNTP64Error;
+
+    /// Converts a [`Duration`] into a [`NTP64`], failing if the duration's number of seconds
+    /// doesn't fit in the 32-bits Seconds part of the NTP64 (i.e. more than [`MAX_NB_SEC`]).
+    ///
+    /// As with the RFC3339 conversions, this isn't perfectly bijective: the nanoseconds are
+    /// truncated down to the nearest representable fraction of second, so a `Duration` with a
+    /// sub-second part may lose up to 1ns when converted back with [`NTP64::to_duration()`].
+    fn try_from(duration: Duration) -> Result<Self, Self::Error> {
         let secs = duration.as_secs();
-        assert!(secs <= MAX_NB_SEC);
+        if secs > MAX_NB_SEC {
+            return Err(ParseNTP64Error {
+                cause: format!(
+                    "Duration {duration:?} cannot be converted to a NTP64: \
+                     {secs} seconds exceeds the maximum of {MAX_NB_SEC}"
+                ),
+            });
+        }
         let nanos: u64 = duration.subsec_nanos().into();
-        NTP64((secs << 32) + ((nanos * FRAC_PER_SEC) / NANO_PER_SEC) + 1)
+        Ok(NTP64((secs << 32) + ((nanos * FRAC_PER_SEC) / NANO_PER_SEC)))
     }
 }
 
@@ -301,6 +503,23 @@ pub struct ParseNTP64Error {
     pub cause: String,
 }
 
+/// The precision to use for the fractional seconds part when formatting a [`NTP64`] to a
+/// RFC3339 representation with [`NTP64::to_string_rfc3339()`].
+#[cfg(feature = "std")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Precision {
+    /// Omit the fraction when it is zero, otherwise show it with nanosecond precision.
+    Smart,
+    /// Never show the fraction.
+    Seconds,
+    /// Always show the fraction, truncated to milliseconds (3 digits).
+    Millis,
+    /// Always show the fraction, truncated to microseconds (6 digits).
+    Micros,
+    /// Always show the fraction with nanosecond precision (9 digits).
+    Nanos,
+}
+
 mod tests {
 
     #[test]
@@ -323,6 +542,19 @@ mod tests {
         assert!(epoch_plus_counter_max.as_secs_f64() < 0.0000000035f64);
     }
 
+    #[test]
+    fn checked_and_saturating_arithmetic() {
+        use crate::*;
+
+        assert_eq!(NTP64(u64::MAX).checked_add(NTP64(1)), None);
+        assert_eq!(NTP64(u64::MAX).checked_add(NTP64(0)), Some(NTP64(u64::MAX)));
+        assert_eq!(NTP64(0).checked_sub(NTP64(1)), None);
+        assert_eq!(NTP64(1).checked_sub(NTP64(1)), Some(NTP64(0)));
+
+        assert_eq!(NTP64(u64::MAX).saturating_add(NTP64(1)), NTP64(u64::MAX));
+        assert_eq!(NTP64(0).saturating_sub(NTP64(1)), NTP64(0));
+    }
+
     #[test]
     fn bijective_to_string() {
         use crate::*;
@@ -336,25 +568,99 @@ mod tests {
         }
     }
 
+    #[test]
+    fn try_from_duration() {
+        use crate::*;
+        use core::time::Duration;
+        use std::convert::TryFrom;
+
+        let d = Duration::new(42, 500_000_000);
+        let t = NTP64::try_from(d).unwrap();
+        assert_eq!(t.to_duration(), d);
+
+        let too_big = Duration::new(ntp64::MAX_NB_SEC + 1, 0);
+        assert!(NTP64::try_from(too_big).is_err());
+
+        // unlike try_from, saturating_from_duration saturates instead of failing
+        let saturated = NTP64::saturating_from_duration(too_big);
+        assert_eq!(saturated.as_secs() as u64, ntp64::MAX_NB_SEC);
+    }
+
     #[test]
     fn rfc3339_conversion() {
         use crate::*;
+        use core::time::Duration;
         use regex::Regex;
+        use std::time::{SystemTime, UNIX_EPOCH};
 
         let rfc3339_regex = Regex::new(
             r"^[0-9][0-9][0-9][0-9]-[0-9][0-9]-[0-9][0-9]T[0-9][0-9]:[0-9][0-9]:[0-9][0-9].[0-9][0-9][0-9][0-9][0-9][0-9][0-9][0-9][0-9]Z$"
         ).unwrap();
 
         let now = SystemTime::now();
-        let t = NTP64::from(now.duration_since(UNIX_EPOCH).unwrap());
+        let t = NTP64::try_from(now.duration_since(UNIX_EPOCH).unwrap()).unwrap();
+        // converting the Duration's nanoseconds to a NTP64 fraction-of-second truncates,
+        // so the resulting time may lag the original by up to 1ns.
+        let max_loss = Duration::from_nanos(1);
 
         let rfc3339 = t.to_string_rfc3339_lossy();
-        assert_eq!(rfc3339, humantime::format_rfc3339_nanos(now).to_string());
         assert!(rfc3339_regex.is_match(&rfc3339));
+        assert!(now.duration_since(t.to_system_time()).unwrap() <= max_loss);
 
         // Test that alternate format "{:#}" displays in RFC3339 format
         let rfc3339_2 = format!("{t:#}");
-        assert_eq!(rfc3339_2, humantime::format_rfc3339_nanos(now).to_string());
+        assert_eq!(rfc3339_2, rfc3339);
         assert!(rfc3339_regex.is_match(&rfc3339_2));
     }
+
+    #[test]
+    fn rfc3339_precision() {
+        use crate::*;
+        use core::time::Duration;
+
+        let with_fraction = NTP64::try_from(Duration::new(42, 500_000_000)).unwrap();
+        assert_eq!(
+            with_fraction.to_string_rfc3339(Precision::Seconds),
+            "1970-01-01T00:00:42Z"
+        );
+        assert_eq!(
+            with_fraction.to_string_rfc3339(Precision::Smart),
+            with_fraction.to_string_rfc3339(Precision::Nanos)
+        );
+
+        let whole_seconds = NTP64::try_from(Duration::new(42, 0)).unwrap();
+        assert_eq!(
+            whole_seconds.to_string_rfc3339(Precision::Smart),
+            whole_seconds.to_string_rfc3339(Precision::Seconds)
+        );
+    }
+
+    #[test]
+    fn strftime_format() {
+        use crate::*;
+        use core::time::Duration;
+
+        let whole_seconds = NTP64::try_from(Duration::new(42, 0)).unwrap();
+        assert_eq!(whole_seconds.format("%H:%M:%S%.f"), "00:00:42");
+        assert_eq!(whole_seconds.format("%H:%M:%S%f"), "00:00:420");
+        assert_eq!(whole_seconds.format("%Y-%m-%d"), "1970-01-01");
+
+        let with_fraction = NTP64::try_from(Duration::new(42, 125_000_000)).unwrap();
+        assert_eq!(with_fraction.format("%H:%M:%S%.f"), "00:00:42.125");
+        assert_eq!(with_fraction.format("%H:%M:%S%f"), "00:00:42125");
+        assert_eq!(with_fraction.format("100%%"), "100%");
+    }
+
+    #[test]
+    fn human_duration() {
+        use crate::*;
+        use core::time::Duration;
+
+        let d = NTP64::try_from(Duration::new(9005, 125_000_000)).unwrap();
+        let s = d.to_human_duration_string();
+        assert_eq!(s, "2h 30m 5s 125ms");
+        assert_eq!(NTP64::parse_human_duration(&s).unwrap(), d);
+
+        assert!(NTP64::parse_human_duration("not a duration").is_err());
+    }
 }